@@ -0,0 +1,370 @@
+#![allow(dead_code)]
+
+use crate::{proc_net, MacAddressError, MacAddressFilter};
+use once_cell::sync::OnceCell;
+use std::ffi::{CStr, CString};
+use std::net::IpAddr;
+use std::os::raw::{c_char, c_int, c_void};
+
+type GetifaddrsFn = unsafe extern "C" fn(*mut *mut libc::ifaddrs) -> c_int;
+type FreeifaddrsFn = unsafe extern "C" fn(*mut libc::ifaddrs);
+
+/// `getifaddrs`/`freeifaddrs`, resolved lazily so we tolerate API levels
+/// where `libc.so` doesn't export them.
+struct LibcIfaddrs {
+    getifaddrs: GetifaddrsFn,
+    freeifaddrs: FreeifaddrsFn,
+}
+
+// The resolved function pointers are plain `extern "C" fn`s; nothing about
+// them is thread-affine.
+unsafe impl Send for LibcIfaddrs {}
+unsafe impl Sync for LibcIfaddrs {}
+
+static LIBC_IFADDRS: OnceCell<Option<LibcIfaddrs>> = OnceCell::new();
+
+/// A single interface's name, index and (if present) MAC address, as
+/// produced by either backend below.
+pub(crate) struct RawInterface {
+    pub(crate) name: String,
+    pub(crate) index: u32,
+    pub(crate) mac: Option<[u8; 6]>,
+    pub(crate) flags: u32,
+}
+
+impl RawInterface {
+    pub(crate) fn is_loopback(&self) -> bool {
+        self.flags & libc::IFF_LOOPBACK as u32 != 0
+    }
+
+    pub(crate) fn is_up(&self) -> bool {
+        let up_and_running = (libc::IFF_UP | libc::IFF_RUNNING) as u32;
+        self.flags & up_and_running == up_and_running
+    }
+}
+
+/// Many Android API levels don't reliably export `getifaddrs`/`freeifaddrs`
+/// from `libc.so` (they were only consistently available starting with
+/// API 24), so resolve them at runtime via `dlopen`/`dlsym` instead of
+/// linking against them directly, and cache the result for the life of the
+/// process.
+fn libc_ifaddrs() -> &'static Option<LibcIfaddrs> {
+    LIBC_IFADDRS.get_or_init(|| unsafe {
+        let soname = CString::new("libc.so").unwrap();
+        let handle = libc::dlopen(soname.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if handle.is_null() {
+            return None;
+        }
+
+        let getifaddrs = resolve(handle, "getifaddrs")?;
+        let freeifaddrs = resolve(handle, "freeifaddrs")?;
+
+        Some(LibcIfaddrs {
+            getifaddrs: std::mem::transmute::<*mut c_void, GetifaddrsFn>(getifaddrs),
+            freeifaddrs: std::mem::transmute::<*mut c_void, FreeifaddrsFn>(freeifaddrs),
+        })
+    })
+}
+
+unsafe fn resolve(handle: *mut c_void, name: &str) -> Option<*mut c_void> {
+    let name = CString::new(name).ok()?;
+    let sym = libc::dlsym(handle, name.as_ptr());
+    if sym.is_null() {
+        None
+    } else {
+        Some(sym)
+    }
+}
+
+/// Walks the `getifaddrs` linked list, pulling the hardware address out of
+/// any `AF_PACKET` entries (the same family Linux reports link-layer
+/// addresses under).
+unsafe fn list_via_getifaddrs(symbols: &LibcIfaddrs) -> Result<Vec<RawInterface>, MacAddressError> {
+    let mut head: *mut libc::ifaddrs = std::ptr::null_mut();
+    if (symbols.getifaddrs)(&mut head) != 0 {
+        return Err(MacAddressError::InternalError);
+    }
+
+    let mut result = Vec::new();
+    let mut ptr = head;
+
+    while !ptr.is_null() {
+        let ifa = &*ptr;
+
+        if !ifa.ifa_name.is_null() && !ifa.ifa_addr.is_null() {
+            let name = CStr::from_ptr(ifa.ifa_name as *const c_char)
+                .to_string_lossy()
+                .into_owned();
+
+            if (*ifa.ifa_addr).sa_family as i32 == libc::AF_PACKET {
+                let sll = ifa.ifa_addr as *const libc::sockaddr_ll;
+                let halen = (*sll).sll_halen as usize;
+                let index = (*sll).sll_ifindex as u32;
+
+                let mac = if halen >= 6 {
+                    let mut bytes = [0u8; 6];
+                    bytes.copy_from_slice(&(*sll).sll_addr[..6]);
+                    Some(bytes)
+                } else {
+                    None
+                };
+
+                result.push(RawInterface {
+                    name,
+                    index,
+                    mac,
+                    flags: ifa.ifa_flags,
+                });
+            }
+        }
+
+        ptr = ifa.ifa_next;
+    }
+
+    (symbols.freeifaddrs)(head);
+
+    Ok(result)
+}
+
+/// Fallback for API levels where `getifaddrs` isn't available: dump
+/// `RTM_GETLINK` over an `AF_NETLINK` socket and read the interface name,
+/// index and hardware address straight out of the `rtnetlink` attributes.
+mod netlink {
+    use super::RawInterface;
+    use crate::MacAddressError;
+    use std::mem;
+
+    const NLMSG_ALIGNTO: usize = 4;
+
+    fn nlmsg_align(len: usize) -> usize {
+        (len + NLMSG_ALIGNTO - 1) & !(NLMSG_ALIGNTO - 1)
+    }
+
+    #[repr(C)]
+    struct NlMsgHdr {
+        nlmsg_len: u32,
+        nlmsg_type: u16,
+        nlmsg_flags: u16,
+        nlmsg_seq: u32,
+        nlmsg_pid: u32,
+    }
+
+    #[repr(C)]
+    struct IfInfoMsg {
+        ifi_family: u8,
+        __ifi_pad: u8,
+        ifi_type: u16,
+        ifi_index: i32,
+        ifi_flags: u32,
+        ifi_change: u32,
+    }
+
+    #[repr(C)]
+    struct RtAttr {
+        rta_len: u16,
+        rta_type: u16,
+    }
+
+    const RTM_NEWLINK: u16 = 16;
+    const RTM_GETLINK: u16 = 18;
+    const NLM_F_REQUEST: u16 = 0x1;
+    const NLM_F_DUMP: u16 = 0x300;
+    const NLMSG_DONE: u16 = 3;
+    const NLMSG_ERROR: u16 = 2;
+    const IFLA_ADDRESS: u16 = 1;
+    const IFLA_IFNAME: u16 = 3;
+
+    pub fn list_interfaces() -> Result<Vec<RawInterface>, MacAddressError> {
+        let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_RAW, libc::NETLINK_ROUTE) };
+        if fd < 0 {
+            return Err(MacAddressError::InternalError);
+        }
+
+        let result = unsafe { dump(fd) };
+
+        unsafe {
+            libc::close(fd);
+        }
+
+        result
+    }
+
+    unsafe fn dump(fd: std::os::raw::c_int) -> Result<Vec<RawInterface>, MacAddressError> {
+        let mut request = [0u8; mem::size_of::<NlMsgHdr>() + mem::size_of::<IfInfoMsg>()];
+
+        let hdr = request.as_mut_ptr() as *mut NlMsgHdr;
+        (*hdr).nlmsg_len = request.len() as u32;
+        (*hdr).nlmsg_type = RTM_GETLINK;
+        (*hdr).nlmsg_flags = NLM_F_REQUEST | NLM_F_DUMP;
+        (*hdr).nlmsg_seq = 1;
+        (*hdr).nlmsg_pid = 0;
+
+        let ifi = request.as_mut_ptr().add(mem::size_of::<NlMsgHdr>()) as *mut IfInfoMsg;
+        (*ifi).ifi_family = libc::AF_PACKET as u8;
+        (*ifi).__ifi_pad = 0;
+        (*ifi).ifi_type = 0;
+        (*ifi).ifi_index = 0;
+        (*ifi).ifi_flags = 0;
+        (*ifi).ifi_change = 0;
+
+        let sent = libc::send(fd, request.as_ptr() as *const _, request.len(), 0);
+        if sent < 0 {
+            return Err(MacAddressError::InternalError);
+        }
+
+        let mut result = Vec::new();
+        let mut buf = vec![0u8; 16 * 1024];
+
+        'recv: loop {
+            let received = libc::recv(fd, buf.as_mut_ptr() as *mut _, buf.len(), 0);
+            if received < 0 {
+                return Err(MacAddressError::InternalError);
+            }
+
+            let mut offset = 0usize;
+            let received = received as usize;
+
+            while offset + mem::size_of::<NlMsgHdr>() <= received {
+                let hdr = buf.as_ptr().add(offset) as *const NlMsgHdr;
+                let msg_len = (*hdr).nlmsg_len as usize;
+
+                if msg_len < mem::size_of::<NlMsgHdr>() {
+                    break;
+                }
+
+                match (*hdr).nlmsg_type {
+                    NLMSG_DONE => break 'recv,
+                    NLMSG_ERROR => return Err(MacAddressError::InternalError),
+                    // Each link in the dump comes back as an `RTM_NEWLINK` message.
+                    RTM_NEWLINK => {
+                        if let Some(interface) = parse_link(buf.as_ptr().add(offset), msg_len) {
+                            result.push(interface);
+                        }
+                    }
+                    _ => {}
+                }
+
+                offset += nlmsg_align(msg_len);
+            }
+        }
+
+        Ok(result)
+    }
+
+    unsafe fn parse_link(msg: *const u8, msg_len: usize) -> Option<RawInterface> {
+        let ifi = msg.add(mem::size_of::<NlMsgHdr>()) as *const IfInfoMsg;
+        let index = (*ifi).ifi_index as u32;
+        let flags = (*ifi).ifi_flags;
+
+        let attrs_start = mem::size_of::<NlMsgHdr>() + nlmsg_align(mem::size_of::<IfInfoMsg>());
+        let mut offset = attrs_start;
+
+        let mut name = None;
+        let mut mac = None;
+
+        while offset + mem::size_of::<RtAttr>() <= msg_len {
+            let rta = msg.add(offset) as *const RtAttr;
+            let rta_len = (*rta).rta_len as usize;
+
+            if rta_len < mem::size_of::<RtAttr>() {
+                break;
+            }
+
+            let payload = msg.add(offset + mem::size_of::<RtAttr>());
+            let payload_len = rta_len - mem::size_of::<RtAttr>();
+
+            match (*rta).rta_type {
+                IFLA_IFNAME => {
+                    let bytes = std::slice::from_raw_parts(payload, payload_len);
+                    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                    name = Some(String::from_utf8_lossy(&bytes[..end]).into_owned());
+                }
+                IFLA_ADDRESS if payload_len >= 6 => {
+                    let bytes = std::slice::from_raw_parts(payload, 6);
+                    let mut out = [0u8; 6];
+                    out.copy_from_slice(bytes);
+                    mac = Some(out);
+                }
+                _ => {}
+            }
+
+            offset += nlmsg_align(rta_len);
+        }
+
+        name.map(|name| RawInterface {
+            name,
+            index,
+            mac,
+            flags,
+        })
+    }
+}
+
+pub(crate) fn list_interfaces() -> Result<Vec<RawInterface>, MacAddressError> {
+    if let Some(symbols) = libc_ifaddrs() {
+        unsafe { list_via_getifaddrs(symbols) }
+    } else {
+        netlink::list_interfaces()
+    }
+}
+
+pub fn get_mac(name: Option<&str>) -> Result<Option<[u8; 6]>, MacAddressError> {
+    for interface in list_interfaces()? {
+        let mac = match interface.mac {
+            Some(mac) => mac,
+            None => continue,
+        };
+
+        if let Some(requested) = name {
+            if interface.name == requested {
+                return Ok(Some(mac));
+            }
+        } else if mac.iter().any(|&x| x != 0) {
+            return Ok(Some(mac));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn get_mac_list() -> Result<Vec<[u8; 6]>, MacAddressError> {
+    Ok(list_interfaces()?
+        .into_iter()
+        .filter_map(|interface| interface.mac)
+        .filter(|mac| mac.iter().any(|&x| x != 0))
+        .collect())
+}
+
+/// Like `get_mac_list`, but consults the interface's real `IFF_LOOPBACK`/
+/// `IFF_UP`/`IFF_RUNNING` flags instead of guessing loopback from all-zero
+/// bytes.
+pub fn get_mac_list_filtered(filter: &MacAddressFilter) -> Result<Vec<[u8; 6]>, MacAddressError> {
+    Ok(list_interfaces()?
+        .into_iter()
+        .filter(|interface| filter.include_loopback || !interface.is_loopback())
+        .filter(|interface| !filter.require_up || interface.is_up())
+        .filter_map(|interface| interface.mac)
+        .collect())
+}
+
+pub fn get_ifname(mac: &[u8; 6]) -> Result<Option<String>, MacAddressError> {
+    for interface in list_interfaces()? {
+        if interface.mac.as_ref() == Some(mac) {
+            return Ok(Some(interface.name));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Resolves the MAC address of another host on the local segment. The
+/// `getifaddrs` availability issues above don't apply here: `/proc/net/arp`
+/// is a plain kernel interface. See `proc_net::mac_of_ip`.
+pub fn mac_of_ip(ip: IpAddr) -> Result<Option<[u8; 6]>, MacAddressError> {
+    proc_net::mac_of_ip(ip)
+}
+
+/// Returns the current default gateway's IP. See `proc_net::default_gateway_ip`.
+pub fn default_gateway_ip() -> Result<Option<IpAddr>, MacAddressError> {
+    proc_net::default_gateway_ip()
+}
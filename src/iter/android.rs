@@ -0,0 +1,83 @@
+use crate::os;
+use crate::{MacAddress, MacAddressError};
+
+/// An iterator over all available MAC addresses on the system.
+///
+/// The Android backend can't walk the interface list lazily the way the
+/// `getifaddrs`-based Unix iterator does, since the symbols it depends on
+/// may have to be resolved via `dlopen`, or the list may come from a
+/// one-shot netlink dump; both are collected eagerly instead.
+pub struct MacAddressIterator {
+    iter: std::vec::IntoIter<MacAddress>,
+}
+
+impl MacAddressIterator {
+    /// Creates a new `MacAddressIterator`.
+    pub fn new() -> Result<MacAddressIterator, MacAddressError> {
+        let macs = os::get_mac_list()?.into_iter().map(MacAddress::new).collect::<Vec<_>>();
+
+        Ok(Self {
+            iter: macs.into_iter(),
+        })
+    }
+}
+
+impl Iterator for MacAddressIterator {
+    type Item = MacAddress;
+
+    fn next(&mut self) -> Option<MacAddress> {
+        self.iter.next()
+    }
+}
+
+/// A network interface paired with the MAC address found on it.
+pub struct NetworkInterface {
+    /// The interface's name, e.g. `wlan0`.
+    pub name: String,
+    /// The MAC address assigned to the interface.
+    pub mac: MacAddress,
+    /// The kernel's interface index for the interface.
+    pub index: u32,
+    /// Whether the interface is flagged `IFF_LOOPBACK`.
+    pub is_loopback: bool,
+    /// Whether the interface is flagged both `IFF_UP` and `IFF_RUNNING`.
+    pub is_up: bool,
+}
+
+/// An iterator over all `(interface name, MAC address)` pairs on the system.
+pub struct NetworkInterfaceIterator {
+    iter: std::vec::IntoIter<NetworkInterface>,
+}
+
+impl NetworkInterfaceIterator {
+    /// Creates a new `NetworkInterfaceIterator`.
+    pub fn new() -> Result<NetworkInterfaceIterator, MacAddressError> {
+        let interfaces = os::list_interfaces()?
+            .into_iter()
+            .filter_map(|interface| {
+                let is_loopback = interface.is_loopback();
+                let is_up = interface.is_up();
+
+                interface.mac.map(|mac| NetworkInterface {
+                    name: interface.name,
+                    mac: MacAddress::new(mac),
+                    index: interface.index,
+                    is_loopback,
+                    is_up,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            iter: interfaces.into_iter(),
+        })
+    }
+}
+
+impl Iterator for NetworkInterfaceIterator {
+    type Item = NetworkInterface;
+
+    fn next(&mut self) -> Option<NetworkInterface> {
+        self.iter.next()
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{MacAddress, MacAddressError};
 use nix::ifaddrs;
+use nix::net::if_::{if_nametoindex, InterfaceFlags};
 
 /// An iterator over all available MAC addresses on the system.
 pub struct MacAddressIterator {
@@ -33,3 +34,66 @@ impl Iterator for MacAddressIterator {
         self.iter.next()
     }
 }
+
+/// A network interface paired with the MAC address found on it.
+pub struct NetworkInterface {
+    /// The interface's name, e.g. `eth0`.
+    pub name: String,
+    /// The MAC address assigned to the interface.
+    pub mac: MacAddress,
+    /// The kernel's interface index for the interface.
+    pub index: u32,
+    /// Whether the interface is flagged `IFF_LOOPBACK`.
+    pub is_loopback: bool,
+    /// Whether the interface is flagged both `IFF_UP` and `IFF_RUNNING`.
+    pub is_up: bool,
+}
+
+/// An iterator over all `(interface name, MAC address)` pairs on the system.
+///
+/// Unlike [`MacAddressIterator`], this keeps the interface name that
+/// `getifaddrs` already handed us (plus its index, looked up with
+/// `if_nametoindex`), so callers that need both don't have
+/// to re-walk the interface list per lookup via [`crate::mac_address_by_name`].
+pub struct NetworkInterfaceIterator {
+    iter: std::iter::FilterMap<
+        ifaddrs::InterfaceAddressIterator,
+        fn(ifaddrs::InterfaceAddress) -> Option<NetworkInterface>,
+    >,
+}
+
+impl NetworkInterfaceIterator {
+    /// Creates a new `NetworkInterfaceIterator`.
+    pub fn new() -> Result<NetworkInterfaceIterator, MacAddressError> {
+        Ok(Self {
+            iter: ifaddrs::getifaddrs()?.filter_map(filter_interfaces),
+        })
+    }
+}
+
+fn filter_interfaces(intf: ifaddrs::InterfaceAddress) -> Option<NetworkInterface> {
+    let address = intf.address?;
+    let link = address.as_link_addr()?;
+    let mac = MacAddress::new(link.addr()?);
+    let index = if_nametoindex(intf.interface_name.as_str()).unwrap_or(0);
+    let is_loopback = intf.flags.contains(InterfaceFlags::IFF_LOOPBACK);
+    let is_up = intf
+        .flags
+        .contains(InterfaceFlags::IFF_UP | InterfaceFlags::IFF_RUNNING);
+
+    Some(NetworkInterface {
+        name: intf.interface_name,
+        mac,
+        index,
+        is_loopback,
+        is_up,
+    })
+}
+
+impl Iterator for NetworkInterfaceIterator {
+    type Item = NetworkInterface;
+
+    fn next(&mut self) -> Option<NetworkInterface> {
+        self.iter.next()
+    }
+}
@@ -0,0 +1,24 @@
+//! Contains iterator types for enumerating the MAC addresses (and, where
+//! available, the owning interfaces) found on the host.
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+))]
+#[path = "linux.rs"]
+mod os;
+
+#[cfg(target_os = "android")]
+#[path = "android.rs"]
+mod os;
+
+#[cfg(windows)]
+#[path = "windows.rs"]
+mod os;
+
+pub use os::{MacAddressIterator, NetworkInterface, NetworkInterfaceIterator};
@@ -2,6 +2,10 @@ use crate::os;
 use crate::{MacAddress, MacAddressError};
 use windows::Win32::NetworkManagement::IpHelper::IP_ADAPTER_ADDRESSES_LH;
 
+// From `Ifdef.h` / `Iptypes.h`; see the matching constants in `os::get_mac_list_filtered`.
+const IF_TYPE_SOFTWARE_LOOPBACK: u32 = 24;
+const IF_OPER_STATUS_UP: i32 = 1;
+
 /// An iterator over all available MAC addresses on the system.
 pub struct MacAddressIterator {
     // So we don't UAF during iteration.
@@ -39,3 +43,71 @@ impl Iterator for MacAddressIterator {
         }
     }
 }
+
+/// A network interface paired with the MAC address found on it.
+pub struct NetworkInterface {
+    /// The interface's friendly name, e.g. `Ethernet`.
+    pub name: String,
+    /// The MAC address assigned to the interface.
+    pub mac: MacAddress,
+    /// Windows' `IfIndex` for the interface.
+    pub index: u32,
+    /// Whether `IfType` reports this as a software loopback adapter.
+    pub is_loopback: bool,
+    /// Whether `OperStatus` reports this adapter as up.
+    pub is_up: bool,
+}
+
+/// An iterator over all `(interface name, MAC address)` pairs on the system.
+///
+/// Unlike [`MacAddressIterator`], this keeps the `FriendlyName` and `IfIndex`
+/// that `GetAdaptersAddresses` already handed us, so callers that need both
+/// don't have to re-walk the adapter list per lookup via `os::get_ifname`.
+pub struct NetworkInterfaceIterator {
+    // So we don't UAF during iteration.
+    _buffer: Vec<u8>,
+    ptr: *mut IP_ADAPTER_ADDRESSES_LH,
+}
+
+impl NetworkInterfaceIterator {
+    /// Creates a new `NetworkInterfaceIterator`.
+    pub fn new() -> Result<NetworkInterfaceIterator, MacAddressError> {
+        let mut adapters = os::get_adapters()?;
+        let ptr = adapters
+            .as_mut_ptr()
+            .cast::<windows::Win32::NetworkManagement::IpHelper::IP_ADAPTER_ADDRESSES_LH>();
+
+        Ok(Self {
+            _buffer: adapters,
+            ptr,
+        })
+    }
+}
+
+impl Iterator for NetworkInterfaceIterator {
+    type Item = NetworkInterface;
+
+    fn next(&mut self) -> Option<NetworkInterface> {
+        if self.ptr.is_null() {
+            None
+        } else {
+            let bytes = unsafe { os::convert_mac_bytes(self.ptr) };
+            let name = unsafe { os::construct_string((*self.ptr).FriendlyName.as_ptr()) }
+                .into_string()
+                .unwrap_or_default();
+            let index = unsafe { (*self.ptr).IfIndex };
+            let is_loopback = unsafe { (*self.ptr).IfType == IF_TYPE_SOFTWARE_LOOPBACK };
+            let is_up = unsafe { (*self.ptr).OperStatus.0 == IF_OPER_STATUS_UP };
+
+            self.ptr = unsafe { (*self.ptr).Next };
+
+            Some(NetworkInterface {
+                name,
+                mac: MacAddress::new(bytes),
+                index,
+                is_loopback,
+                is_up,
+            })
+        }
+    }
+}
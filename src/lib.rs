@@ -0,0 +1,367 @@
+//! A crate that lets you get a network interface's MAC address on Linux,
+//! macOS, iOS, the BSDs, Android and Windows.
+//!
+//! Example
+//!
+//! ```rust
+//! use mac_address::get_mac_address;
+//!
+//! match get_mac_address() {
+//!     Ok(Some(ma)) => {
+//!         println!("MAC addr = {}", ma);
+//!         println!("bytes = {:?}", ma.bytes());
+//!     }
+//!     Ok(None) => println!("No MAC address found."),
+//!     Err(e) => println!("{:?}", e),
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+pub mod iter;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly",
+))]
+#[path = "linux.rs"]
+mod os;
+
+// Android doesn't reliably export `getifaddrs`/`freeifaddrs` from `libc.so`
+// across API levels, so it gets its own backend; see `android.rs`.
+#[cfg(target_os = "android")]
+#[path = "android.rs"]
+mod os;
+
+// `/proc/net/arp` and `/proc/net/route` parsing shared by the `linux.rs` and
+// `android.rs` backends; neither file differs between the two. `/proc` is a
+// Linux kernel interface, so unlike `os` above this isn't compiled for the
+// BSDs/macOS/iOS, which don't have it.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod proc_net;
+
+#[cfg(windows)]
+#[path = "windows.rs"]
+mod os;
+
+/// Contains the individual bytes of the MAC address.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct MacAddress {
+    bytes: [u8; 6],
+}
+
+impl MacAddress {
+    /// Create a new `MacAddress` from the provided bytes.
+    pub fn new(bytes: [u8; 6]) -> MacAddress {
+        MacAddress { bytes }
+    }
+
+    /// Returns the array of MAC address bytes.
+    pub fn bytes(&self) -> [u8; 6] {
+        self.bytes
+    }
+
+    /// Parses a MAC address, auto-detecting the notation: colon-separated
+    /// (`aa:bb:cc:dd:ee:ff`), hyphen-separated (`AA-BB-CC-DD-EE-FF`, as
+    /// Windows shows), or Cisco dotted-triplet (`aabb.ccdd.eeff`). Upper and
+    /// lower case hex digits are both accepted. This is equivalent to
+    /// `s.parse()`.
+    pub fn parse(s: &str) -> Result<MacAddress, MacParseError> {
+        s.parse()
+    }
+
+    /// Formats the address hyphen-separated and uppercase, e.g.
+    /// `AA-BB-CC-DD-EE-FF`, as Windows shows it.
+    pub fn to_hyphenated(&self) -> String {
+        let b = self.bytes;
+        format!(
+            "{:02X}-{:02X}-{:02X}-{:02X}-{:02X}-{:02X}",
+            b[0], b[1], b[2], b[3], b[4], b[5]
+        )
+    }
+
+    /// Formats the address as a Cisco dotted-triplet, e.g. `aabb.ccdd.eeff`.
+    pub fn to_dotted(&self) -> String {
+        let b = self.bytes;
+        format!(
+            "{:02x}{:02x}.{:02x}{:02x}.{:02x}{:02x}",
+            b[0], b[1], b[2], b[3], b[4], b[5]
+        )
+    }
+
+    /// Expands the address into its modified EUI-64 form (as used to derive
+    /// IPv6 interface identifiers): splits the address around an inserted
+    /// `FF:FE`, and flips the universal/local bit of the first byte.
+    pub fn to_eui64(&self) -> [u8; 8] {
+        let b = self.bytes;
+        [b[0] ^ 0x02, b[1], b[2], 0xff, 0xfe, b[3], b[4], b[5]]
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let octets = self.bytes;
+        write!(
+            f,
+            "{:<02x}:{:<02x}:{:<02x}:{:<02x}:{:<02x}:{:<02x}",
+            octets[0], octets[1], octets[2], octets[3], octets[4], octets[5]
+        )
+    }
+}
+
+impl fmt::Debug for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = MacParseError;
+
+    /// Parses a colon-separated (`aa:bb:cc:dd:ee:ff`), hyphen-separated
+    /// (`AA-BB-CC-DD-EE-FF`), or Cisco dotted-triplet (`aabb.ccdd.eeff`) MAC
+    /// address, auto-detecting which notation was used from its separator.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(':') {
+            parse_grouped(s, ':', 2)
+        } else if s.contains('-') {
+            parse_grouped(s, '-', 2)
+        } else if s.contains('.') {
+            parse_grouped(s, '.', 4)
+        } else {
+            Err(MacParseError::InvalidCharacter)
+        }
+    }
+}
+
+/// Parses a MAC address made of groups of `group_len` hex digits joined by
+/// `sep`, e.g. `sep = ':'`/`group_len = 2` for colon notation, or
+/// `sep = '.'`/`group_len = 4` for Cisco's dotted triplets.
+fn parse_grouped(s: &str, sep: char, group_len: usize) -> Result<MacAddress, MacParseError> {
+    let mut hex = String::with_capacity(12);
+
+    for group in s.split(sep) {
+        if group.len() != group_len || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(MacParseError::InvalidCharacter);
+        }
+
+        hex.push_str(group);
+    }
+
+    if hex.len() != 12 {
+        return Err(MacParseError::InvalidByteCount);
+    }
+
+    let mut bytes = [0u8; 6];
+
+    for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let chunk = std::str::from_utf8(chunk).map_err(|_| MacParseError::InvalidCharacter)?;
+        *byte = u8::from_str_radix(chunk, 16).map_err(|_| MacParseError::InvalidCharacter)?;
+    }
+
+    Ok(MacAddress::new(bytes))
+}
+
+/// Error for when a `MacAddress` instance can't be created.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MacParseError {
+    InvalidCharacter,
+    InvalidByteCount,
+}
+
+impl fmt::Display for MacParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            MacParseError::InvalidCharacter => "Encountered invalid character",
+            MacParseError::InvalidByteCount => "Found invalid number of bytes",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for MacParseError {}
+
+/// Internal error types for this crate.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MacAddressError {
+    InternalError,
+}
+
+impl fmt::Display for MacAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Internal Error")
+    }
+}
+
+impl Error for MacAddressError {
+    fn description(&self) -> &str {
+        "MacAddressError internal error"
+    }
+}
+
+#[cfg(unix)]
+impl From<nix::Error> for MacAddressError {
+    fn from(_: nix::Error) -> Self {
+        MacAddressError::InternalError
+    }
+}
+
+/// Gets the MAC address of the first network device found. Since this
+/// can vary across platforms, and it's not guaranteed to find the
+/// 'right' one, especially if the host has multiple network devices.
+pub fn get_mac_address() -> Result<Option<MacAddress>, MacAddressError> {
+    match os::get_mac(None) {
+        Ok(Some(bytes)) => Ok(Some(MacAddress::new(bytes))),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Gets the MAC address of the network device with the given name.
+pub fn mac_address_by_name(name: &str) -> Result<Option<MacAddress>, MacAddressError> {
+    match os::get_mac(Some(name)) {
+        Ok(Some(bytes)) => Ok(Some(MacAddress::new(bytes))),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Options controlling which interfaces `get_mac_list_filtered` considers.
+///
+/// `get_mac_list` decides "skip loopback" by checking whether the MAC
+/// address bytes are all zero. That heuristic conflates "has no hardware
+/// address" with "is loopback", so this filter consults the interface's
+/// real flags (`IFF_LOOPBACK`/`IFF_UP` on Unix, `IfType`/`OperStatus` on
+/// Windows) instead.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MacAddressFilter {
+    /// Include interfaces flagged as loopback. Defaults to `false`.
+    pub include_loopback: bool,
+    /// Only include interfaces that are administratively and operationally
+    /// up. Defaults to `false`.
+    pub require_up: bool,
+}
+
+/// Like `get_mac_address`, but returns every matching MAC address and
+/// consults real interface flags rather than the `get_mac_list` heuristic.
+pub fn get_mac_list_filtered(filter: MacAddressFilter) -> Result<Vec<MacAddress>, MacAddressError> {
+    Ok(os::get_mac_list_filtered(&filter)?
+        .into_iter()
+        .map(MacAddress::new)
+        .collect())
+}
+
+/// Resolves the MAC address of another host on the local segment, e.g. a
+/// LAN neighbor or the default gateway, rather than one of this machine's
+/// own network devices.
+///
+/// Returns `Ok(None)` if the address couldn't be resolved, which usually
+/// means the host is unreachable or hasn't appeared in the ARP/neighbor
+/// cache yet. On macOS, iOS and the BSDs no neighbor-cache lookup is made
+/// and this always returns `Ok(None)`.
+pub fn mac_of_ip(ip: IpAddr) -> Result<Option<MacAddress>, MacAddressError> {
+    match os::mac_of_ip(ip) {
+        Ok(Some(bytes)) => Ok(Some(MacAddress::new(bytes))),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Gets the MAC address of the current default gateway, for presence/uplink
+/// detection. Builds on the same adapter/route-table walk as the rest of
+/// this crate, followed by a `mac_of_ip` lookup. On macOS, iOS and the BSDs
+/// this always returns `Ok(None)`.
+pub fn default_gateway_mac() -> Result<Option<MacAddress>, MacAddressError> {
+    match os::default_gateway_ip()? {
+        Some(ip) => mac_of_ip(ip),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; 6] = [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+
+    #[test]
+    fn parses_colon_notation() {
+        assert_eq!(MacAddress::parse("aa:bb:cc:dd:ee:ff").unwrap().bytes(), BYTES);
+        assert_eq!(MacAddress::parse("AA:BB:CC:DD:EE:FF").unwrap().bytes(), BYTES);
+    }
+
+    #[test]
+    fn parses_hyphen_notation() {
+        assert_eq!(MacAddress::parse("aa-bb-cc-dd-ee-ff").unwrap().bytes(), BYTES);
+        assert_eq!(MacAddress::parse("AA-BB-CC-DD-EE-FF").unwrap().bytes(), BYTES);
+    }
+
+    #[test]
+    fn parses_cisco_dotted_notation() {
+        assert_eq!(MacAddress::parse("aabb.ccdd.eeff").unwrap().bytes(), BYTES);
+        assert_eq!(MacAddress::parse("AABB.CCDD.EEFF").unwrap().bytes(), BYTES);
+    }
+
+    #[test]
+    fn rejects_wrong_byte_count() {
+        assert_eq!(
+            MacAddress::parse("aa:bb:cc:dd:ee"),
+            Err(MacParseError::InvalidByteCount)
+        );
+        assert_eq!(
+            MacAddress::parse("aa:bb:cc:dd:ee:ff:00"),
+            Err(MacParseError::InvalidByteCount)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        assert_eq!(
+            MacAddress::parse("zz:bb:cc:dd:ee:ff"),
+            Err(MacParseError::InvalidCharacter)
+        );
+        assert_eq!(MacAddress::parse("not a mac"), Err(MacParseError::InvalidCharacter));
+    }
+
+    #[test]
+    fn rejects_leading_sign_in_group() {
+        // `u8::from_str_radix` accepts a leading `+`/`-`; groups must be
+        // rejected before they ever reach it.
+        assert_eq!(
+            MacAddress::parse("+a:+b:+c:+d:+e:+f"),
+            Err(MacParseError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn formats_hyphenated() {
+        assert_eq!(MacAddress::new(BYTES).to_hyphenated(), "AA-BB-CC-DD-EE-FF");
+    }
+
+    #[test]
+    fn formats_dotted() {
+        assert_eq!(MacAddress::new(BYTES).to_dotted(), "aabb.ccdd.eeff");
+    }
+
+    #[test]
+    fn display_is_lowercase_colon() {
+        assert_eq!(MacAddress::new(BYTES).to_string(), "aa:bb:cc:dd:ee:ff");
+    }
+
+    #[test]
+    fn expands_to_eui64() {
+        assert_eq!(
+            MacAddress::new(BYTES).to_eui64(),
+            [0xa8, 0xbb, 0xcc, 0xff, 0xfe, 0xdd, 0xee, 0xff]
+        );
+    }
+}
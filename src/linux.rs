@@ -1,7 +1,11 @@
 #![allow(dead_code)]
 
-use crate::MacAddressError;
+#[cfg(target_os = "linux")]
+use crate::proc_net;
+use crate::{MacAddressError, MacAddressFilter};
 use nix::ifaddrs::*;
+use nix::net::if_::InterfaceFlags;
+use std::net::IpAddr;
 
 /// Uses the `getifaddrs` call to retrieve a list of network interfaces on the
 /// host device and returns the first MAC address listed that isn't
@@ -48,6 +52,74 @@ pub fn get_mac_list() -> Result<Vec<[u8; 6]>, MacAddressError> {
     Ok(result)
 }
 
+/// Like `get_mac_list`, but consults the interface's real `InterfaceFlags`
+/// (`IFF_LOOPBACK`, `IFF_UP`, `IFF_RUNNING`) instead of guessing loopback
+/// from all-zero bytes, which also misfires on any interface that simply
+/// has no hardware address to report.
+pub fn get_mac_list_filtered(filter: &MacAddressFilter) -> Result<Vec<[u8; 6]>, MacAddressError> {
+    let mut result = vec![];
+
+    for interface in getifaddrs()? {
+        if !filter.include_loopback && interface.flags.contains(InterfaceFlags::IFF_LOOPBACK) {
+            continue;
+        }
+
+        if filter.require_up
+            && !interface
+                .flags
+                .contains(InterfaceFlags::IFF_UP | InterfaceFlags::IFF_RUNNING)
+        {
+            continue;
+        }
+
+        if let Some(address) = &interface.address {
+            if let Some(link) = address.as_link_addr() {
+                if let Some(bytes) = link.addr() {
+                    result.push(bytes);
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Resolves the MAC address of another host on the local segment, as
+/// opposed to `get_mac`/`get_mac_list` which only look at this machine's
+/// own adapters. See `proc_net::mac_of_ip`.
+///
+/// This lookup only exists on Linux: this backend is also compiled for macOS,
+/// iOS and the BSDs, which don't have `/proc/net/arp`. On those platforms
+/// `mac_of_ip`/`default_gateway_ip` are stubs that always return `Ok(None)`;
+/// no neighbor-cache or routing-table lookup is made there.
+#[cfg(target_os = "linux")]
+pub fn mac_of_ip(ip: IpAddr) -> Result<Option<[u8; 6]>, MacAddressError> {
+    proc_net::mac_of_ip(ip)
+}
+
+/// Returns the current default gateway's IP. See `proc_net::default_gateway_ip`.
+///
+/// Only reads the routing table on Linux; see `mac_of_ip`'s doc comment for
+/// what the other platforms this backend is compiled for return.
+#[cfg(target_os = "linux")]
+pub fn default_gateway_ip() -> Result<Option<IpAddr>, MacAddressError> {
+    proc_net::default_gateway_ip()
+}
+
+/// Stub for the non-Linux members of this backend (macOS, iOS, the BSDs):
+/// always reports the address as unresolved rather than erroring. No
+/// neighbor-cache lookup is made; see the Linux `mac_of_ip`'s doc comment.
+#[cfg(not(target_os = "linux"))]
+pub fn mac_of_ip(_ip: IpAddr) -> Result<Option<[u8; 6]>, MacAddressError> {
+    Ok(None)
+}
+
+/// Stub for the non-Linux members of this backend; see `mac_of_ip` above.
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway_ip() -> Result<Option<IpAddr>, MacAddressError> {
+    Ok(None)
+}
+
 pub fn get_ifname(mac: &[u8; 6]) -> Result<Option<String>, MacAddressError> {
     let ifiter = getifaddrs()?;
 
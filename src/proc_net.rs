@@ -0,0 +1,116 @@
+//! `/proc/net/arp` and `/proc/net/route` parsing shared by the `linux.rs`
+//! and `android.rs` backends. Neither file is Linux-kernel-specific in a way
+//! that differs between the two: both read the same `/proc` files, so the
+//! parsing lives here once instead of being copied into each backend.
+
+use crate::MacAddressError;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Resolves the MAC address of another host on the local segment by reading
+/// the kernel's ARP/neighbor cache at `/proc/net/arp`, as opposed to
+/// `get_mac`/`get_mac_list` which only look at this machine's own adapters.
+///
+/// Returns `Ok(None)` if the address could not be resolved, e.g. because the
+/// host is unreachable or hasn't been ARPed yet.
+pub(crate) fn mac_of_ip(ip: IpAddr) -> Result<Option<[u8; 6]>, MacAddressError> {
+    let target = match ip {
+        IpAddr::V4(v4) => v4.to_string(),
+        // /proc/net/arp only carries IPv4 neighbor entries.
+        IpAddr::V6(_) => return Ok(None),
+    };
+
+    // A missing or unreadable ARP cache (container, restricted sandbox, or
+    // simply no entries yet) means we can't resolve anything, not that
+    // something went wrong — match the documented `Ok(None)` contract above
+    // rather than bubbling up an error.
+    let contents = match fs::read_to_string("/proc/net/arp") {
+        Ok(contents) => contents,
+        Err(_) => return Ok(None),
+    };
+
+    // Skip the header line; columns are whitespace-separated:
+    // IP address, HW type, Flags, HW address, Mask, Device
+    for line in contents.lines().skip(1) {
+        let mut columns = line.split_whitespace();
+        let ip_column = match columns.next() {
+            Some(ip_column) => ip_column,
+            None => continue,
+        };
+
+        if ip_column != target {
+            continue;
+        }
+
+        let hw_address = match columns.nth(2) {
+            Some(hw_address) => hw_address,
+            None => continue,
+        };
+
+        if let Some(bytes) = parse_hw_address(hw_address) {
+            return Ok(Some(bytes));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads the default route's gateway IP out of the kernel routing table at
+/// `/proc/net/route`. The MAC is then resolved the same way as any other
+/// host, via `mac_of_ip`/the ARP cache.
+pub(crate) fn default_gateway_ip() -> Result<Option<IpAddr>, MacAddressError> {
+    // A missing or unreadable routing table means we simply can't find a
+    // default route, not that something went wrong.
+    let contents = match fs::read_to_string("/proc/net/route") {
+        Ok(contents) => contents,
+        Err(_) => return Ok(None),
+    };
+
+    // Columns are tab-separated: Iface, Destination, Gateway, Flags, ...
+    // Destination/Gateway are little-endian hex-encoded u32s; the default
+    // route is the one with a zero destination.
+    for line in contents.lines().skip(1) {
+        let mut columns = line.split_whitespace();
+        let _iface = columns.next();
+
+        let destination = match columns.next() {
+            Some(destination) => destination,
+            None => continue,
+        };
+
+        if destination != "00000000" {
+            continue;
+        }
+
+        let gateway = match columns.next() {
+            Some(gateway) => gateway,
+            None => continue,
+        };
+
+        if let Ok(raw) = u32::from_str_radix(gateway, 16) {
+            if raw != 0 {
+                return Ok(Some(IpAddr::V4(Ipv4Addr::from(raw.to_le_bytes()))));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_hw_address(hw_address: &str) -> Option<[u8; 6]> {
+    let parts: Vec<&str> = hw_address.split(':').collect();
+    if parts.len() != 6 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 6];
+    for (byte, part) in bytes.iter_mut().zip(parts) {
+        *byte = u8::from_str_radix(part, 16).ok()?;
+    }
+
+    if bytes.iter().all(|&x| x == 0) {
+        None
+    } else {
+        Some(bytes)
+    }
+}
@@ -1,14 +1,22 @@
 use core::convert::TryInto;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::{ffi::OsString, os::windows::ffi::OsStringExt, slice};
-use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::Foundation::{ERROR_SUCCESS, NO_ERROR};
 use windows::Win32::NetworkManagement::IpHelper::{
-    GetAdaptersAddresses, GET_ADAPTERS_ADDRESSES_FLAGS, IP_ADAPTER_ADDRESSES_LH,
+    GetAdaptersAddresses, SendARP, GET_ADAPTERS_ADDRESSES_FLAGS, IP_ADAPTER_ADDRESSES_LH,
 };
-use windows::Win32::Networking::WinSock::AF_UNSPEC;
+use windows::Win32::Networking::WinSock::{SOCKADDR_IN, SOCKADDR_IN6, SOCKET_ADDRESS, AF_INET, AF_INET6, AF_UNSPEC};
 
-use crate::MacAddressError;
+use crate::{MacAddressError, MacAddressFilter};
 
 const GAA_FLAG_NONE: GET_ADAPTERS_ADDRESSES_FLAGS = GET_ADAPTERS_ADDRESSES_FLAGS(0x0000);
+const GAA_FLAG_INCLUDE_GATEWAYS: GET_ADAPTERS_ADDRESSES_FLAGS = GET_ADAPTERS_ADDRESSES_FLAGS(0x0080);
+
+// From `Ifdef.h` / `Iptypes.h`; pulled in as raw constants rather than
+// bindings since `windows-rs` doesn't expose every `IFTYPE`/`IF_OPER_STATUS`
+// variant.
+const IF_TYPE_SOFTWARE_LOOPBACK: u32 = 24;
+const IF_OPER_STATUS_UP: i32 = 1;
 
 /// Uses bindings to the `Iphlpapi.h` Windows header to fetch the interface devices
 /// list with [GetAdaptersAddresses][https://msdn.microsoft.com/en-us/library/windows/desktop/aa365915(v=vs.85).aspx]
@@ -77,6 +85,35 @@ pub fn get_mac_list() -> Result<Vec<[u8; 6]>, MacAddressError> {
     Ok(result)
 }
 
+/// Like `get_mac_list`, but consults the adapter's real `IfType`/`OperStatus`
+/// instead of guessing loopback from all-zero bytes, which also catches any
+/// adapter that simply has no hardware address to report.
+pub fn get_mac_list_filtered(filter: &MacAddressFilter) -> Result<Vec<[u8; 6]>, MacAddressError> {
+    let mut adapters = get_adapters()?;
+    let mut ptr = adapters.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH;
+
+    let mut result = vec![];
+
+    loop {
+        if ptr.is_null() {
+            break;
+        }
+
+        let is_loopback = unsafe { (*ptr).IfType == IF_TYPE_SOFTWARE_LOOPBACK };
+        let is_up = unsafe { (*ptr).OperStatus.0 == IF_OPER_STATUS_UP };
+
+        let skip = (!filter.include_loopback && is_loopback) || (filter.require_up && !is_up);
+
+        if !skip {
+            result.push(unsafe { convert_mac_bytes(ptr) });
+        }
+
+        ptr = unsafe { (*ptr).Next };
+    }
+
+    Ok(result)
+}
+
 pub fn get_ifname(mac: &[u8; 6]) -> Result<Option<String>, MacAddressError> {
     let mut adapters = get_adapters()?;
     // Pointer to the current location in the linked list
@@ -105,17 +142,100 @@ pub fn get_ifname(mac: &[u8; 6]) -> Result<Option<String>, MacAddressError> {
     Ok(None)
 }
 
+/// Uses `SendARP` from `Iphlpapi.h` to resolve the MAC address of another
+/// host on the local segment, as opposed to `get_mac`/`get_mac_list` which
+/// only look at this machine's own adapters.
+///
+/// Returns `Ok(None)` if the address could not be resolved, e.g. because the
+/// host is unreachable or not on the local subnet.
+pub fn mac_of_ip(ip: IpAddr) -> Result<Option<[u8; 6]>, MacAddressError> {
+    let dst = match ip {
+        IpAddr::V4(v4) => u32::from_ne_bytes(v4.octets()),
+        // SendARP only supports IPv4; there's no ARP equivalent to resolve here.
+        IpAddr::V6(_) => return Ok(None),
+    };
+
+    let mut mac_buf = [0u8; 6];
+    let mut out_len: u32 = mac_buf.len() as u32;
+
+    let result = unsafe { SendARP(dst, 0, mac_buf.as_mut_ptr() as *mut _, &mut out_len) };
+
+    if result == NO_ERROR.0 {
+        Ok(Some(mac_buf))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Uses `GetAdaptersAddresses` with `GAA_FLAG_INCLUDE_GATEWAYS` to read each
+/// adapter's `FirstGatewayAddress` list and returns a gateway IP. The MAC is
+/// then resolved the same way as any other host, via `mac_of_ip`/`SendARP`,
+/// which only supports IPv4 — so on dual-stack hosts an `AF_INET` gateway is
+/// preferred over an `AF_INET6` one whenever both are present, rather than
+/// just taking whichever family shows up first in the list.
+pub fn default_gateway_ip() -> Result<Option<IpAddr>, MacAddressError> {
+    let mut adapters = get_adapters_with_flags(GAA_FLAG_INCLUDE_GATEWAYS)?;
+    let mut ptr = adapters.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH;
+
+    let mut fallback = None;
+
+    while !ptr.is_null() {
+        let mut gateway_ptr = unsafe { (*ptr).FirstGatewayAddress };
+
+        while !gateway_ptr.is_null() {
+            if let Some(ip) = unsafe { socket_address_to_ip((*gateway_ptr).Address) } {
+                if ip.is_ipv4() {
+                    return Ok(Some(ip));
+                }
+
+                fallback.get_or_insert(ip);
+            }
+
+            gateway_ptr = unsafe { (*gateway_ptr).Next };
+        }
+
+        ptr = unsafe { (*ptr).Next };
+    }
+
+    Ok(fallback)
+}
+
+unsafe fn socket_address_to_ip(address: SOCKET_ADDRESS) -> Option<IpAddr> {
+    if address.lpSockaddr.is_null() {
+        return None;
+    }
+
+    match (*address.lpSockaddr).sa_family {
+        AF_INET => {
+            let addr = *(address.lpSockaddr as *const SOCKADDR_IN);
+            let octets = addr.sin_addr.S_un.S_addr.to_ne_bytes();
+            Some(IpAddr::V4(Ipv4Addr::from(octets)))
+        }
+        AF_INET6 => {
+            let addr = *(address.lpSockaddr as *const SOCKADDR_IN6);
+            Some(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.u.Byte)))
+        }
+        _ => None,
+    }
+}
+
 /// Copy over the 6 MAC address bytes to the buffer.
 pub(crate) unsafe fn convert_mac_bytes(ptr: *mut IP_ADAPTER_ADDRESSES_LH) -> [u8; 6] {
     ((*ptr).PhysicalAddress)[..6].try_into().unwrap()
 }
 
 pub(crate) fn get_adapters() -> Result<Vec<u8>, MacAddressError> {
+    get_adapters_with_flags(GAA_FLAG_NONE)
+}
+
+fn get_adapters_with_flags(
+    flags: GET_ADAPTERS_ADDRESSES_FLAGS,
+) -> Result<Vec<u8>, MacAddressError> {
     let mut buf_len = 0;
 
     // This will get the number of bytes we need to allocate for all devices
     unsafe {
-        GetAdaptersAddresses(AF_UNSPEC.0 as u32, GAA_FLAG_NONE, None, None, &mut buf_len);
+        GetAdaptersAddresses(AF_UNSPEC.0 as u32, flags, None, None, &mut buf_len);
     }
 
     // Allocate `buf_len` bytes, and create a raw pointer to it
@@ -128,7 +248,7 @@ pub(crate) fn get_adapters() -> Result<Vec<u8>, MacAddressError> {
             // [IN] Family
             AF_UNSPEC.0 as u32,
             // [IN] Flags
-            GAA_FLAG_NONE,
+            flags,
             // [IN] Reserved
             None,
             // [INOUT] AdapterAddresses
@@ -146,7 +266,7 @@ pub(crate) fn get_adapters() -> Result<Vec<u8>, MacAddressError> {
     Ok(adapters_list)
 }
 
-unsafe fn construct_string(ptr: *mut u16) -> OsString {
+pub(crate) unsafe fn construct_string(ptr: *mut u16) -> OsString {
     let slice = slice::from_raw_parts(ptr, get_null_position(ptr));
     OsStringExt::from_wide(slice)
 }